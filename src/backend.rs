@@ -0,0 +1,230 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::{Path, PathBuf};
+
+/// Abstracts over where a [`crate::FileBackedLfuCache`] actually stores its
+/// bytes, so the LFU/decay machinery doesn't have to know or care whether
+/// it's talking to a real filesystem, an in-memory map, or anything else.
+///
+/// Errors are reported as [`io::Error`] regardless of what actually backs a
+/// given implementation, so that [`crate::Error::Io`] stays meaningful across
+/// backends; an implementation whose errors don't already fit should wrap
+/// them with [`io::Error::other`] (or a specific [`io::ErrorKind`] where one
+/// applies, as `read` below relies on for "not found": the cache maps
+/// [`io::ErrorKind::NotFound`] from `read` to [`crate::Error::NotFound`]).
+#[async_trait]
+pub trait StorageBackend: Send + Sync + 'static {
+    /// Open `path` for reading.
+    async fn read(&self, path: &Path) -> io::Result<Pin<Box<dyn AsyncRead + Send>>>;
+
+    /// Open `path` for writing, creating or truncating it as needed.
+    async fn write(&self, path: &Path) -> io::Result<Pin<Box<dyn AsyncWrite + Send>>>;
+
+    /// Remove `path`. Implementations should treat a missing path as a no-op
+    /// success, as [`FsBackend`] and [`MemBackend`] both do.
+    async fn remove(&self, path: &Path) -> io::Result<()>;
+
+    /// List every path currently stored under `dir`.
+    async fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Ensure `dir` exists, creating it (and any missing parents) if needed.
+    ///
+    /// Called once per namespace at cache construction time. The default is
+    /// a no-op; backends with a notion of "directory" (like [`FsBackend`])
+    /// should override this.
+    async fn create_dir(&self, _dir: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Whether `root` is a sane place for this backend to keep its data,
+    /// checked once at cache construction time (see [`crate::Error::Init`]).
+    ///
+    /// The default accepts anything; backends with a notion of "directory"
+    /// (like [`FsBackend`]) should override this.
+    async fn validate_root(&self, _root: &Path) -> bool {
+        true
+    }
+}
+
+/// The default backend: reads and writes real files via `tokio::fs`.
+#[derive(Debug, Clone, Copy)]
+pub struct FsBackend {
+    /// Whether [`StorageBackend::write`] `fsync`s before its atomic rename.
+    /// See [`crate::AsyncFileRepr::flush_to_disk_durable`] for the same knob
+    /// at the trait-default level. On by default.
+    pub durable: bool,
+}
+impl Default for FsBackend {
+    fn default() -> Self {
+        Self { durable: true }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for FsBackend {
+    async fn read(&self, path: &Path) -> io::Result<Pin<Box<dyn AsyncRead + Send>>> {
+        let file = tokio::fs::File::open(path).await?;
+        Ok(Box::pin(file))
+    }
+
+    async fn write(&self, path: &Path) -> io::Result<Pin<Box<dyn AsyncWrite + Send>>> {
+        let tmp_path = crate::traits::sibling_tmp_path(path);
+        let file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)
+            .await?;
+        Ok(Box::pin(FsWriter {
+            file: Some(file),
+            tmp_path,
+            final_path: path.to_path_buf(),
+            durable: self.durable,
+            finish: None,
+        }))
+    }
+
+    async fn remove(&self, path: &Path) -> io::Result<()> {
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut read_dir = tokio::fs::read_dir(dir).await?;
+        let mut paths = Vec::new();
+        while let Some(entry) = read_dir.next_entry().await? {
+            paths.push(entry.path());
+        }
+        Ok(paths)
+    }
+
+    async fn create_dir(&self, dir: &Path) -> io::Result<()> {
+        tokio::fs::create_dir_all(dir).await
+    }
+
+    async fn validate_root(&self, root: &Path) -> bool {
+        root.is_dir()
+    }
+}
+
+/// An [`AsyncWrite`] that forwards straight through to an already-open
+/// handle on a sibling temp file (so payloads never fully buffer in
+/// memory), then `fsync`s and atomically renames that file over the target
+/// path once the caller calls [`tokio::io::AsyncWriteExt::shutdown`] — the
+/// same crash-safety discipline as [`crate::AsyncFileRepr::flush_to_disk_durable`].
+struct FsWriter {
+    file: Option<tokio::fs::File>,
+    tmp_path: PathBuf,
+    final_path: PathBuf,
+    durable: bool,
+    finish: Option<Pin<Box<dyn Future<Output = io::Result<()>> + Send>>>,
+}
+impl AsyncWrite for FsWriter {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let file = self.get_mut().file.as_mut().expect("FsWriter polled after shutdown");
+        Pin::new(file).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let file = self.get_mut().file.as_mut().expect("FsWriter polled after shutdown");
+        Pin::new(file).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let finish = this.finish.get_or_insert_with(|| {
+            let file = this.file.take();
+            let tmp_path = this.tmp_path.clone();
+            let final_path = this.final_path.clone();
+            let durable = this.durable;
+            Box::pin(async move {
+                if let Some(file) = file {
+                    if durable {
+                        file.sync_all().await?;
+                    }
+                    drop(file);
+                }
+                tokio::fs::rename(&tmp_path, &final_path).await
+            })
+        });
+        finish.as_mut().poll(cx)
+    }
+}
+
+/// A single [`MemBackend`] file's contents, behind its own lock so an
+/// in-flight [`MemWriter`] doesn't need to hold the whole backend locked.
+type MemSlot = Arc<Mutex<Vec<u8>>>;
+
+/// An in-memory backend useful for tests and ephemeral caches: a
+/// `HashMap<PathBuf, Vec<u8>>` behind a lock, with no actual disk I/O.
+#[derive(Debug, Clone, Default)]
+pub struct MemBackend {
+    files: Arc<Mutex<HashMap<PathBuf, MemSlot>>>,
+}
+
+#[async_trait]
+impl StorageBackend for MemBackend {
+    async fn read(&self, path: &Path) -> io::Result<Pin<Box<dyn AsyncRead + Send>>> {
+        let slot = {
+            let files = self.files.lock().expect("MemBackend mutex should never be poisoned");
+            files.get(path).cloned()
+        };
+        let Some(slot) = slot else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("{path:?} not found in MemBackend")));
+        };
+        let bytes = slot.lock().expect("MemBackend mutex should never be poisoned").clone();
+        Ok(Box::pin(io::Cursor::new(bytes)))
+    }
+
+    async fn write(&self, path: &Path) -> io::Result<Pin<Box<dyn AsyncWrite + Send>>> {
+        let slot = Arc::new(Mutex::new(Vec::new()));
+        self.files
+            .lock()
+            .expect("MemBackend mutex should never be poisoned")
+            .insert(path.to_path_buf(), Arc::clone(&slot));
+        Ok(Box::pin(MemWriter(slot)))
+    }
+
+    async fn remove(&self, path: &Path) -> io::Result<()> {
+        self.files.lock().expect("MemBackend mutex should never be poisoned").remove(path);
+        Ok(())
+    }
+
+    async fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let files = self.files.lock().expect("MemBackend mutex should never be poisoned");
+        Ok(files.keys().filter(|path| path.parent() == Some(dir)).cloned().collect())
+    }
+}
+
+/// An [`AsyncWrite`] that appends directly into a [`MemBackend`] slot, with no
+/// temp-file dance: the slot is visible to [`StorageBackend::read`] as soon
+/// as [`StorageBackend::write`] returns, so a concurrent read sees whatever
+/// bytes have been written so far.
+struct MemWriter(MemSlot);
+impl AsyncWrite for MemWriter {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.lock().expect("MemBackend mutex should never be poisoned").extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    task::spawn_blocking,
+};
+
+use crate::traits::AsyncFileRepr;
+
+/// A newtype wrapper giving any `T: Serialize + DeserializeOwned` an
+/// [`AsyncFileRepr`] implementation for free, backed by `bincode`'s standard
+/// configuration.
+///
+/// Encoding/decoding runs inside [`spawn_blocking`] so a large payload
+/// doesn't stall the async runtime's worker threads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bincoded<T>(pub T);
+impl<T> Bincoded<T> {
+    /// Unwrap the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T> From<T> for Bincoded<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+/// The error type produced when a [`Bincoded<T>`] fails to (de)serialise.
+#[derive(Debug, thiserror::Error)]
+pub enum BincodeError {
+    /// An I/O error occurred while reading/writing the underlying stream.
+    #[error("I/O error while (de)serialising with bincode: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The byte stream could not be decoded.
+    #[error("failed to decode with bincode: {0}")]
+    Decode(#[from] bincode::error::DecodeError),
+
+    /// The value could not be encoded.
+    #[error("failed to encode with bincode: {0}")]
+    Encode(#[from] bincode::error::EncodeError),
+}
+
+#[async_trait]
+impl<T> AsyncFileRepr for Bincoded<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    type Err = BincodeError;
+
+    async fn load<R>(mut reader: R) -> Result<Self, Self::Err>
+    where
+        R: Send + Unpin + AsyncRead,
+    {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+
+        let value = spawn_blocking(move || {
+            bincode::serde::decode_from_slice::<T, _>(&buf, bincode::config::standard())
+                .map(|(value, _consumed)| value)
+        })
+        .await
+        .expect("bincode decode task panicked")?;
+
+        Ok(Self(value))
+    }
+
+    async fn flush<W>(self: &Arc<Self>, mut writer: W) -> Result<(), Self::Err>
+    where
+        W: Send + Unpin + AsyncWrite,
+    {
+        let this = Arc::clone(self);
+        let bytes = spawn_blocking(move || {
+            bincode::serde::encode_to_vec(&this.0, bincode::config::standard())
+        })
+        .await
+        .expect("bincode encode task panicked")?;
+
+        writer.write_all(&bytes).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+}
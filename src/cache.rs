@@ -0,0 +1,715 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::{
+    io::{AsyncRead, AsyncWriteExt},
+    sync::RwLock,
+};
+
+use crate::{
+    backend::{FsBackend, StorageBackend},
+    traits::{AsyncFileRepr, Key},
+    Error, Path, PathBuf,
+};
+
+/// Per-entry decay bookkeeping backing the frecency eviction policy.
+///
+/// `weight` is only ever accurate as of `last_touch`; use
+/// [`DecayState::decayed_weight`] to read the value as it stands at some
+/// later `now` without mutating the entry.
+#[derive(Debug)]
+struct DecayState {
+    weight: f64,
+    last_touch: Instant,
+}
+impl DecayState {
+    fn touched_at(now: Instant) -> Self {
+        Self {
+            weight: 1.0,
+            last_touch: now,
+        }
+    }
+
+    /// Decay `weight` up to `now`, fold in a fresh access, and advance
+    /// `last_touch` to `now`.
+    fn touch(&mut self, half_life: Duration, now: Instant) {
+        self.weight = self.decayed_weight(half_life, now) + 1.0;
+        self.last_touch = now;
+    }
+
+    /// The weight as it would read at `now`, without mutating any state.
+    ///
+    /// This lets idle entries keep decaying for the purposes of eviction
+    /// comparisons, even though nothing touches them to trigger a recompute.
+    fn decayed_weight(&self, half_life: Duration, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last_touch).as_secs_f64();
+        self.weight * 2f64.powf(-elapsed / half_life.as_secs_f64())
+    }
+
+    fn is_expired(&self, ttl: Option<Duration>, now: Instant) -> bool {
+        match ttl {
+            Some(ttl) => now.saturating_duration_since(self.last_touch) > ttl,
+            None => false,
+        }
+    }
+}
+
+struct Entry<V> {
+    value: Arc<V>,
+    decay: DecayState,
+    /// [`AsyncFileRepr::heap_size`] of `value`, captured at insert time.
+    size: u64,
+}
+
+/// Marks `key` as having an outstanding [`FileBackedLfuCache::get_reader`]
+/// stream for as long as this guard is alive, and releases it on drop so
+/// `remove`/eviction can resume targeting the key once the last such reader
+/// is done.
+///
+/// Readers are refcounted rather than tracked as a set membership, since two
+/// callers can legitimately hold concurrent readers over the same key.
+struct OpenReaderGuard<K: Eq + Hash> {
+    key: K,
+    open_readers: Arc<Mutex<HashMap<K, usize>>>,
+}
+impl<K: Eq + Hash> Drop for OpenReaderGuard<K> {
+    fn drop(&mut self) {
+        let mut open_readers = self
+            .open_readers
+            .lock()
+            .expect("open reader count mutex should never be poisoned");
+        if let Some(count) = open_readers.get_mut(&self.key) {
+            *count -= 1;
+            if *count == 0 {
+                open_readers.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// An [`AsyncRead`] that keeps its [`OpenReaderGuard`] alive for as long as
+/// it is, so the cache knows not to evict or remove the underlying key.
+struct GuardedReader<K: Eq + Hash> {
+    inner: Pin<Box<dyn AsyncRead + Send>>,
+    _guard: OpenReaderGuard<K>,
+}
+impl<K: Eq + Hash + Unpin> AsyncRead for GuardedReader<K> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.get_mut().inner.as_mut().poll_read(cx, buf)
+    }
+}
+
+/// An async, file-backed LFU cache with a time-decayed "frecency" eviction
+/// policy: entries gain weight each time they're touched, but that weight
+/// decays exponentially with `half_life`, so a once-popular-but-now-stale
+/// entry loses out to a recently-touched one.
+///
+/// Generic over the [`StorageBackend`] `B` that actually holds the bytes;
+/// defaults to [`FsBackend`], i.e. real files under `backing_dir`.
+pub struct FileBackedLfuCache<K, V, B = FsBackend>
+where
+    K: Key + Eq + Hash + Clone + Unpin,
+    V: AsyncFileRepr,
+    B: StorageBackend,
+{
+    backing_dir: PathBuf,
+    backend: B,
+    capacity: usize,
+    half_life: Duration,
+    ttl: Option<Duration>,
+    byte_capacity: Option<u64>,
+    current_bytes: AtomicU64,
+    entries: RwLock<HashMap<K, Entry<V>>>,
+    /// Keys with an outstanding [`FileBackedLfuCache::get_reader`] stream,
+    /// and how many such streams are currently open; these are temporarily
+    /// immutable (see [`Error::Immutable`]).
+    open_readers: Arc<Mutex<HashMap<K, usize>>>,
+}
+impl<K, V> FileBackedLfuCache<K, V, FsBackend>
+where
+    K: Key + Eq + Hash + Clone + Unpin,
+    V: AsyncFileRepr + Send + Sync,
+{
+    /// Create a new cache backed by real files under `backing_dir`, holding
+    /// at most `capacity` items in memory, with frecency scores decaying at
+    /// `half_life`.
+    ///
+    /// Returns [`Error::Init`] if `backing_dir` does not resolve to a directory.
+    pub async fn new(
+        backing_dir: impl Into<PathBuf>,
+        capacity: usize,
+        half_life: Duration,
+    ) -> Result<Self, Error<V::Err>> {
+        Self::with_backend(FsBackend::default(), backing_dir, capacity, half_life).await
+    }
+
+    /// Skip `fsync` on flush for higher throughput, at the cost of the
+    /// crash-safety guarantee described on [`AsyncFileRepr::flush_to_disk`].
+    /// Durable by default.
+    pub fn with_durable(mut self, durable: bool) -> Self {
+        self.backend.durable = durable;
+        self
+    }
+}
+impl<K, V, B> FileBackedLfuCache<K, V, B>
+where
+    K: Key + Eq + Hash + Clone + Unpin,
+    V: AsyncFileRepr + Send + Sync,
+    B: StorageBackend,
+{
+    /// Create a new cache using a custom [`StorageBackend`] `backend`,
+    /// rooted at `backing_dir`, holding at most `capacity` items in memory,
+    /// with frecency scores decaying at `half_life`.
+    ///
+    /// Returns [`Error::Init`] if `backend` rejects `backing_dir` as a root
+    /// (see [`StorageBackend::validate_root`]).
+    pub async fn with_backend(
+        backend: B,
+        backing_dir: impl Into<PathBuf>,
+        capacity: usize,
+        half_life: Duration,
+    ) -> Result<Self, Error<V::Err>> {
+        let backing_dir = backing_dir.into();
+        if !backend.validate_root(&backing_dir).await {
+            return Err(Error::Init(backing_dir));
+        }
+
+        let namespace_dir = backing_dir.join(K::namespace());
+        backend.create_dir(&namespace_dir).await?;
+        Self::clear_stray_tmp_files(&backend, &namespace_dir).await?;
+
+        Ok(Self {
+            backing_dir,
+            backend,
+            capacity,
+            half_life,
+            ttl: None,
+            byte_capacity: None,
+            current_bytes: AtomicU64::new(0),
+            entries: RwLock::new(HashMap::new()),
+            open_readers: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Impose a hard TTL: entries untouched for longer than `ttl` are
+    /// considered [`Error::Expired`] regardless of their decayed weight.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Additionally bound the cache by estimated resident size: once the
+    /// sum of [`AsyncFileRepr::heap_size`] across cached values exceeds
+    /// `max_bytes`, the lowest-priority entries are evicted until it fits,
+    /// on top of (not instead of) the item-count capacity.
+    pub fn with_byte_capacity(mut self, max_bytes: u64) -> Self {
+        self.byte_capacity = Some(max_bytes);
+        self
+    }
+
+    /// Remove any `.tmp-*` entries left behind in `namespace_dir` by a flush
+    /// that crashed before its rename completed.
+    async fn clear_stray_tmp_files(backend: &B, namespace_dir: &Path) -> Result<(), Error<V::Err>> {
+        for path in backend.list(namespace_dir).await? {
+            if path.file_name().is_some_and(|name| name.to_string_lossy().contains(".tmp-")) {
+                backend.remove(&path).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The configured in-memory item capacity.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The configured decay half-life.
+    pub fn half_life(&self) -> Duration {
+        self.half_life
+    }
+
+    /// The configured hard TTL, if any.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// The configured byte-capacity bound, if any.
+    pub fn capacity_bytes(&self) -> Option<u64> {
+        self.byte_capacity
+    }
+
+    /// The estimated total resident size, in bytes, of all cached values.
+    pub fn current_bytes(&self) -> u64 {
+        self.current_bytes.load(Ordering::Relaxed)
+    }
+
+    /// The namespaced subdirectory `K`'s files live under, so different key
+    /// types can safely share one `backing_dir` (see [`Key::namespace`]).
+    fn namespace_dir(&self) -> PathBuf {
+        self.backing_dir.join(K::namespace())
+    }
+
+    fn path_for(&self, key: &K) -> PathBuf {
+        self.namespace_dir().join(key.as_filename())
+    }
+
+    /// Insert `value` under `key`, then evict lower-priority entries until
+    /// the cache is back within capacity.
+    pub async fn insert(&self, key: K, value: V) -> Result<(), Error<V::Err>> {
+        let now = Instant::now();
+        let size = value.heap_size() as u64;
+        let old_size = {
+            let mut entries = self.entries.write().await;
+            entries
+                .insert(
+                    key,
+                    Entry {
+                        value: Arc::new(value),
+                        decay: DecayState::touched_at(now),
+                        size,
+                    },
+                )
+                .map(|old| old.size)
+        };
+        if let Some(old_size) = old_size {
+            self.current_bytes.fetch_sub(old_size, Ordering::Relaxed);
+        }
+        self.current_bytes.fetch_add(size, Ordering::Relaxed);
+        self.evict_to_capacity().await
+    }
+
+    /// Look up `key`, touching its decay state on a hit.
+    ///
+    /// On a memory miss, falls back to loading `key` from the backing store
+    /// via [`AsyncFileRepr::load`] and re-admits it into the cache with a
+    /// fresh [`DecayState`] before returning it.
+    ///
+    /// Returns [`Error::Expired`] if the entry's TTL has elapsed, or
+    /// [`Error::NotFound`] if the key is resident neither in memory nor on disk.
+    pub async fn get(&self, key: &K) -> Result<Arc<V>, Error<V::Err>> {
+        let now = Instant::now();
+        {
+            let mut entries = self.entries.write().await;
+            if let Some(entry) = entries.get_mut(key) {
+                if entry.decay.is_expired(self.ttl, now) {
+                    return Err(Error::Expired(Box::new(key.clone())));
+                }
+                entry.decay.touch(self.half_life, now);
+                return Ok(Arc::clone(&entry.value));
+            }
+        }
+
+        let path = self.path_for(key);
+        let reader = self.backend.read(&path).await.map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                Error::NotFound(Box::new(key.clone()))
+            } else {
+                Error::Io(err)
+            }
+        })?;
+        let value = Arc::new(V::load(reader).await.map_err(Error::Serde)?);
+        let size = value.heap_size() as u64;
+
+        let old_size = {
+            let mut entries = self.entries.write().await;
+            entries
+                .insert(
+                    key.clone(),
+                    Entry {
+                        value: Arc::clone(&value),
+                        decay: DecayState::touched_at(now),
+                        size,
+                    },
+                )
+                .map(|old| old.size)
+        };
+        if let Some(old_size) = old_size {
+            self.current_bytes.fetch_sub(old_size, Ordering::Relaxed);
+        }
+        self.current_bytes.fetch_add(size, Ordering::Relaxed);
+        self.evict_to_capacity().await?;
+
+        Ok(value)
+    }
+
+    /// Remove `key` from the cache without flushing it to disk.
+    ///
+    /// Returns [`Error::Immutable`] if `key` has an outstanding
+    /// [`FileBackedLfuCache::get_reader`] stream.
+    pub async fn remove(&self, key: &K) -> Result<Option<Arc<V>>, Error<V::Err>> {
+        if self.is_locked(key) {
+            return Err(Error::Immutable(Box::new(key.clone())));
+        }
+
+        let mut entries = self.entries.write().await;
+        let Some(entry) = entries.remove(key) else {
+            return Ok(None);
+        };
+        self.current_bytes.fetch_sub(entry.size, Ordering::Relaxed);
+        Ok(Some(entry.value))
+    }
+
+    fn is_locked(&self, key: &K) -> bool {
+        self.open_readers
+            .lock()
+            .expect("open reader count mutex should never be poisoned")
+            .contains_key(key)
+    }
+
+    /// Stream `key`'s value out without materialising it as a deserialised
+    /// `V`. On a cache hit this streams [`AsyncFileRepr::stream_out`]; on a
+    /// miss it opens the backing file directly. While the returned reader is
+    /// alive, `key` is temporarily immutable (see [`Error::Immutable`]).
+    pub async fn get_reader(
+        &self,
+        key: &K,
+    ) -> Result<Pin<Box<dyn AsyncRead + Send>>, Error<V::Err>> {
+        // Register the guard before doing any I/O, so the key is immutable
+        // for the lifetime of the returned reader on both the hit and miss
+        // paths below.
+        let guard = self.acquire_reader_guard(key);
+
+        let hit = {
+            let entries = self.entries.read().await;
+            entries.get(key).map(|entry| Arc::clone(&entry.value))
+        };
+        if let Some(value) = hit {
+            let reader = value.stream_out().await.map_err(Error::Serde)?;
+            return Ok(Box::pin(GuardedReader {
+                inner: reader,
+                _guard: guard,
+            }));
+        }
+
+        let path = self.path_for(key);
+        let reader = self.backend.read(&path).await.map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                Error::NotFound(Box::new(key.clone()))
+            } else {
+                Error::Io(err)
+            }
+        })?;
+
+        Ok(Box::pin(GuardedReader {
+            inner: reader,
+            _guard: guard,
+        }))
+    }
+
+    /// Mark `key` as having one more outstanding reader, returning a guard
+    /// that undoes this once dropped.
+    fn acquire_reader_guard(&self, key: &K) -> OpenReaderGuard<K> {
+        *self
+            .open_readers
+            .lock()
+            .expect("open reader count mutex should never be poisoned")
+            .entry(key.clone())
+            .or_insert(0) += 1;
+        OpenReaderGuard {
+            key: key.clone(),
+            open_readers: Arc::clone(&self.open_readers),
+        }
+    }
+
+    /// Stream `reader` straight to `key`'s backing store via the cache's
+    /// [`StorageBackend`], without holding the value in memory.
+    ///
+    /// Returns [`Error::Immutable`] if `key` has an outstanding
+    /// [`FileBackedLfuCache::get_reader`] stream.
+    pub async fn put_reader<R>(&self, key: &K, mut reader: R) -> Result<(), Error<V::Err>>
+    where
+        R: AsyncRead + Send + Unpin,
+    {
+        if self.is_locked(key) {
+            return Err(Error::Immutable(Box::new(key.clone())));
+        }
+
+        // Whatever `V` might be resident in memory for `key` no longer
+        // matches the bytes we're about to stream in; drop it so a later
+        // eviction doesn't clobber this write with a stale serialization.
+        let old_entry = {
+            let mut entries = self.entries.write().await;
+            entries.remove(key)
+        };
+        if let Some(entry) = old_entry {
+            self.current_bytes.fetch_sub(entry.size, Ordering::Relaxed);
+        }
+
+        let mut writer = self.backend.write(&self.path_for(key)).await?;
+        tokio::io::copy(&mut reader, &mut writer).await?;
+        writer.shutdown().await?;
+        Ok(())
+    }
+
+    /// Evict the lowest-priority entries (smallest decayed weight, expired
+    /// entries first) until the cache holds no more than `capacity` items
+    /// and, if a byte capacity is configured, fits within it too, flushing
+    /// each evicted entry to disk first.
+    ///
+    /// Keys with an outstanding [`FileBackedLfuCache::get_reader`] stream are
+    /// never picked, so if only such keys remain the loop stops while the
+    /// cache is still over capacity.
+    async fn evict_to_capacity(&self) -> Result<(), Error<V::Err>> {
+        loop {
+            let now = Instant::now();
+            let victim = {
+                let entries = self.entries.read().await;
+                let over_count = entries.len() > self.capacity;
+                let over_bytes = self
+                    .byte_capacity
+                    .is_some_and(|cap| self.current_bytes.load(Ordering::Relaxed) > cap);
+                if !over_count && !over_bytes {
+                    None
+                } else {
+                    entries
+                        .iter()
+                        .filter(|(key, _)| !self.is_locked(key))
+                        .min_by(|(_, a), (_, b)| {
+                            let expired_a = a.decay.is_expired(self.ttl, now);
+                            let expired_b = b.decay.is_expired(self.ttl, now);
+                            expired_b.cmp(&expired_a).then_with(|| {
+                                a.decay
+                                    .decayed_weight(self.half_life, now)
+                                    .total_cmp(&b.decay.decayed_weight(self.half_life, now))
+                            })
+                        })
+                        .map(|(key, _)| key.clone())
+                }
+            };
+
+            let Some(victim) = victim else {
+                break;
+            };
+            self.evict(&victim).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn evict(&self, key: &K) -> Result<(), Error<V::Err>> {
+        let entry = {
+            let mut entries = self.entries.write().await;
+            entries.remove(key)
+        };
+
+        if let Some(entry) = entry {
+            self.current_bytes.fetch_sub(entry.size, Ordering::Relaxed);
+            let mut writer = self.backend.write(&self.path_for(key)).await?;
+            entry.value.flush(&mut writer).await.map_err(Error::Serde)?;
+            writer.shutdown().await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decayed_weight_halves_after_one_half_life() {
+        let half_life = Duration::from_secs(60);
+        let state = DecayState::touched_at(Instant::now());
+        let later = state.last_touch + half_life;
+
+        assert!((state.decayed_weight(half_life, later) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn touch_folds_the_decayed_weight_into_a_fresh_access() {
+        let half_life = Duration::from_secs(60);
+        let mut state = DecayState::touched_at(Instant::now());
+        let later = state.last_touch + half_life;
+
+        state.touch(half_life, later);
+
+        assert!((state.weight - 1.5).abs() < 1e-9);
+        assert_eq!(state.last_touch, later);
+    }
+
+    #[test]
+    fn is_expired_respects_the_configured_ttl() {
+        let state = DecayState::touched_at(Instant::now());
+        let ttl = Duration::from_secs(30);
+
+        assert!(!state.is_expired(Some(ttl), state.last_touch + Duration::from_secs(10)));
+        assert!(state.is_expired(Some(ttl), state.last_touch + Duration::from_secs(31)));
+        assert!(!state.is_expired(None, state.last_touch + Duration::from_secs(1000)));
+    }
+
+    use std::io::Cursor;
+
+    use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+    use crate::backend::MemBackend;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct TestKey(u64);
+    impl Key for TestKey {
+        fn namespace() -> &'static str {
+            "test"
+        }
+
+        fn new() -> Self {
+            Self(fastrand::u64(..))
+        }
+
+        fn as_filename(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    struct TestValue(Vec<u8>);
+    #[async_trait::async_trait]
+    impl AsyncFileRepr for TestValue {
+        type Err = std::io::Error;
+
+        fn heap_size(&self) -> usize {
+            self.0.len()
+        }
+
+        async fn load<R>(mut reader: R) -> Result<Self, Self::Err>
+        where
+            R: Send + Unpin + AsyncRead,
+        {
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf).await?;
+            Ok(Self(buf))
+        }
+
+        async fn flush<W>(self: &Arc<Self>, mut writer: W) -> Result<(), Self::Err>
+        where
+            W: Send + Unpin + AsyncWrite,
+        {
+            writer.write_all(&self.0).await?;
+            writer.flush().await
+        }
+    }
+
+    async fn test_cache(capacity: usize) -> FileBackedLfuCache<TestKey, TestValue, MemBackend> {
+        FileBackedLfuCache::with_backend(
+            MemBackend::default(),
+            PathBuf::from("/test-cache"),
+            capacity,
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn insert_then_get_returns_the_value() {
+        let cache = test_cache(10).await;
+        let key = TestKey(1);
+        cache.insert(key.clone(), TestValue(b"hello".to_vec())).await.unwrap();
+
+        let value = cache.get(&key).await.unwrap();
+        assert_eq!(value.0, b"hello");
+    }
+
+    #[tokio::test]
+    async fn re_inserting_a_key_does_not_inflate_current_bytes() {
+        let cache = test_cache(10).await;
+        let key = TestKey(1);
+
+        cache.insert(key.clone(), TestValue(vec![0u8; 10])).await.unwrap();
+        cache.insert(key.clone(), TestValue(vec![0u8; 4])).await.unwrap();
+
+        assert_eq!(cache.current_bytes(), 4);
+    }
+
+    #[tokio::test]
+    async fn over_capacity_insert_evicts_the_lowest_weight_entry_to_the_backend() {
+        let cache = test_cache(1).await;
+        let first = TestKey(1);
+        let second = TestKey(2);
+
+        cache.insert(first.clone(), TestValue(b"first".to_vec())).await.unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert(second.clone(), TestValue(b"second".to_vec())).await.unwrap();
+
+        // `first` was evicted from memory, but flushed to the backend, so the
+        // raw-bytes lookup should still find it there.
+        let mut reader = cache.get_reader(&first).await.unwrap();
+        let mut contents = Vec::new();
+        reader.read_to_end(&mut contents).await.unwrap();
+        assert_eq!(contents, b"first");
+
+        assert_eq!(cache.get(&second).await.unwrap().0, b"second");
+    }
+
+    #[tokio::test]
+    async fn get_reloads_an_evicted_entry_from_the_backend() {
+        let cache = test_cache(1).await;
+        let first = TestKey(1);
+        let second = TestKey(2);
+
+        cache.insert(first.clone(), TestValue(b"first".to_vec())).await.unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        cache.insert(second.clone(), TestValue(b"second".to_vec())).await.unwrap();
+
+        // `get` should transparently reload `first` from the backend and
+        // re-admit it into memory, rather than reporting it missing forever.
+        let reloaded = cache.get(&first).await.unwrap();
+        assert_eq!(reloaded.0, b"first");
+        assert!(cache.remove(&first).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn get_on_a_truly_absent_key_returns_not_found() {
+        let cache = test_cache(10).await;
+        assert!(matches!(cache.get(&TestKey(42)).await, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn remove_returns_and_forgets_the_value() {
+        let cache = test_cache(10).await;
+        let key = TestKey(1);
+        cache.insert(key.clone(), TestValue(b"hello".to_vec())).await.unwrap();
+
+        let removed = cache.remove(&key).await.unwrap();
+        assert_eq!(removed.unwrap().0, b"hello");
+        assert_eq!(cache.current_bytes(), 0);
+        assert!(cache.remove(&key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn get_reader_locks_the_key_until_dropped() {
+        let cache = test_cache(10).await;
+        let key = TestKey(1);
+        cache.insert(key.clone(), TestValue(b"hello".to_vec())).await.unwrap();
+
+        let reader = cache.get_reader(&key).await.unwrap();
+        assert!(matches!(cache.remove(&key).await, Err(Error::Immutable(_))));
+
+        drop(reader);
+        assert!(cache.remove(&key).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn put_reader_refuses_to_overwrite_a_locked_key() {
+        let cache = test_cache(10).await;
+        let key = TestKey(1);
+        cache.insert(key.clone(), TestValue(b"hello".to_vec())).await.unwrap();
+
+        let reader = cache.get_reader(&key).await.unwrap();
+        let result = cache.put_reader(&key, Cursor::new(b"world".to_vec())).await;
+        assert!(matches!(result, Err(Error::Immutable(_))));
+
+        drop(reader);
+        cache.put_reader(&key, Cursor::new(b"world".to_vec())).await.unwrap();
+    }
+}
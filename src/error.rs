@@ -35,6 +35,12 @@ where
     /// This can happen if you are holding a reference elsewhere, or if this item
     /// is in the process of being flushed to disk.
     Immutable(Box<dyn Key>),
+
+    /// An item with this key was found, but its TTL has elapsed.
+    ///
+    /// This is distinct from [`Error::NotFound`]: the item is still physically
+    /// present, it is just considered stale.
+    Expired(Box<dyn Key>),
 }
 impl<E> fmt::Display for Error<E>
 where
@@ -59,6 +65,7 @@ where
             Immutable(key) => format!(
                 "An item with key {key:?} is temporarily immutable due to outstanding references"
             ),
+            Expired(key) => format!("An item with key {key:?} was found, but has expired"),
         };
 
         write!(f, "{repr}")
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    task::spawn_blocking,
+};
+
+use crate::traits::AsyncFileRepr;
+
+/// Like [`crate::Bincoded`], but stores the value as human-readable JSON
+/// instead of `bincode`. Handy when you want to be able to inspect or hand-edit
+/// the on-disk cache files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jsoned<T>(pub T);
+impl<T> Jsoned<T> {
+    /// Unwrap the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+impl<T> From<T> for Jsoned<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[async_trait]
+impl<T> AsyncFileRepr for Jsoned<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    type Err = serde_json::Error;
+
+    async fn load<R>(mut reader: R) -> Result<Self, Self::Err>
+    where
+        R: Send + Unpin + AsyncRead,
+    {
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .map_err(serde::de::Error::custom)?;
+
+        let value =
+            spawn_blocking(move || serde_json::from_slice(&buf)).await.expect("serde_json deserialise task panicked")?;
+        Ok(Self(value))
+    }
+
+    async fn flush<W>(self: &Arc<Self>, mut writer: W) -> Result<(), Self::Err>
+    where
+        W: Send + Unpin + AsyncWrite,
+    {
+        let this = Arc::clone(self);
+        let bytes = spawn_blocking(move || serde_json::to_vec(&this.0))
+            .await
+            .expect("serde_json serialise task panicked")?;
+
+        writer.write_all(&bytes).await.map_err(serde::ser::Error::custom)?;
+        writer.flush().await.map_err(serde::ser::Error::custom)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+//! `decay-cache`: an async, file-backed LFU cache with a time-decayed
+//! frecency eviction policy.
+
+mod backend;
+#[cfg(feature = "serde-bincode")]
+mod bincoded;
+mod cache;
+mod error;
+#[cfg(feature = "serde-json")]
+mod jsoned;
+mod traits;
+
+pub use backend::{FsBackend, MemBackend, StorageBackend};
+#[cfg(feature = "serde-bincode")]
+pub use bincoded::{BincodeError, Bincoded};
+pub use cache::FileBackedLfuCache;
+pub use error::Error;
+#[cfg(feature = "serde-json")]
+pub use jsoned::Jsoned;
+pub use traits::{AsyncFileRepr, Key};
+
+pub use std::path::{Path, PathBuf};
@@ -1,4 +1,10 @@
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::Debug,
+    io::Cursor,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
 
 use async_trait::async_trait;
 use tokio::{
@@ -6,7 +12,7 @@ use tokio::{
     io::{AsyncRead, AsyncWrite},
 };
 
-use crate::{Error, Path};
+use crate::{Error, Path, PathBuf};
 
 /// A datatype that can be used as the access key for cached items.
 ///
@@ -17,6 +23,20 @@ pub trait Key
 where
     Self: Debug + Send + Sync + 'static,
 {
+    /// The subdirectory (relative to a cache's `backing_dir`) this key
+    /// type's files live under.
+    ///
+    /// This keeps multiple [`crate::FileBackedLfuCache`] instances - for
+    /// different key/value types, or for key types that happen to stringify
+    /// the same way - from clobbering each other's files when they share one
+    /// root directory.
+    ///
+    /// This is a method rather than an associated const so that `Key` stays
+    /// object-safe (the crate relies on `Box<dyn Key>` in [`crate::Error`]).
+    fn namespace() -> &'static str
+    where
+        Self: Sized;
+
     /// Generate a new, unique key.
     fn new() -> Self
     where
@@ -27,6 +47,10 @@ where
 }
 #[cfg(feature = "uuid-as-key")]
 impl Key for uuid::Uuid {
+    fn namespace() -> &'static str {
+        "uuid"
+    }
+
     fn new() -> Self {
         uuid::Uuid::new_v4()
     }
@@ -45,6 +69,16 @@ where
 {
     type Err: std::error::Error;
 
+    /// A heuristic estimate, in bytes, of how much memory this value occupies.
+    ///
+    /// Used by byte-capacity-bounded caches to decide when to evict. The
+    /// default is a rough lower bound from `size_of::<Self>()` alone and
+    /// ignores heap allocations; override it for types like `Vec` or `String`
+    /// where that matters.
+    fn heap_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
     /// Load (deserialise) the data structure into memory asynchronously.
     ///
     /// If you wish to perform non-trivial computation/conversion in this function,
@@ -61,6 +95,22 @@ where
     where
         W: Send + Unpin + AsyncWrite;
 
+    /// Stream this value's on-disk representation out, without requiring the
+    /// caller to hold the fully-deserialised value in memory.
+    ///
+    /// The default implementation just buffers the output of [`AsyncFileRepr::flush`]
+    /// in memory and hands back a reader over that buffer, which defeats the
+    /// purpose for payloads too large to fit in memory; override it for such
+    /// types by streaming from whatever backs them directly.
+    async fn stream_out(self: &Arc<Self>) -> Result<Pin<Box<dyn AsyncRead + Send>>, Self::Err>
+    where
+        Self: Send + Sync,
+    {
+        let sink = VecSink::default();
+        self.flush(sink.clone()).await?;
+        Ok(Box::pin(Cursor::new(sink.into_inner())))
+    }
+
     /// Load (deserialise) the data structure from disk.
     async fn load_from_disk(
         path: impl AsRef<Path> + Send + Sync,
@@ -71,6 +121,11 @@ where
     }
 
     /// Flush (serialise) the data structure to disk.
+    ///
+    /// This writes to a sibling temp file, `fsync`s it, then atomically
+    /// renames it over `path`, so a crash mid-flush can never leave a
+    /// half-written file at `path`. Equivalent to calling
+    /// [`AsyncFileRepr::flush_to_disk_durable`] with `durable: true`.
     async fn flush_to_disk(
         self: &Arc<Self>,
         path: impl AsRef<Path> + Send + Sync,
@@ -78,21 +133,181 @@ where
     where
         Self: Send,
     {
+        self.flush_to_disk_durable(path, true).await
+    }
+
+    /// As [`AsyncFileRepr::flush_to_disk`], but lets the caller opt out of
+    /// the final `fsync` (`durable: false`) for higher throughput at the
+    /// cost of the crash-safety guarantee.
+    async fn flush_to_disk_durable(
+        self: &Arc<Self>,
+        path: impl AsRef<Path> + Send + Sync,
+        durable: bool,
+    ) -> Result<(), Error<Self::Err>>
+    where
+        Self: Send,
+    {
+        let path = path.as_ref();
+        let tmp_path = sibling_tmp_path(path);
+
         let file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(path.as_ref())
+            .open(&tmp_path)
             .await?;
+        // Keep a handle to `fsync` once the writer above is done with it.
+        let synced_file = file.try_clone().await?;
+
         self.flush(file).await.map_err(Error::Serde)?;
+        if durable {
+            synced_file.sync_all().await?;
+        }
+        drop(synced_file);
+
+        fs::rename(&tmp_path, path).await?;
         Ok(())
     }
 
-    /// Delete the data structure from disk.
+    /// Delete the data structure from disk, along with any stray
+    /// `.tmp-*` sibling left behind by a flush that crashed before its
+    /// rename completed.
     ///
     /// Override this method if you wish to perform extra cleanup before deletion.
     async fn delete(path: impl AsRef<Path> + Send + Sync) -> Result<(), Error<Self::Err>> {
-        fs::remove_file(path.as_ref()).await?;
+        let path = path.as_ref();
+        remove_stray_tmp_files(path).await?;
+        fs::remove_file(path).await?;
         Ok(())
     }
 }
+
+/// Build the sibling temp path `flush_to_disk_durable` writes to before
+/// renaming it over the real file, e.g. `foo.json` -> `foo.json.tmp-00000000001a2b3c`.
+pub(crate) fn sibling_tmp_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{file_name}.tmp-{:016x}", fastrand::u64(..)))
+}
+
+/// Remove any `.tmp-*` sibling of `path` left over from a flush that was
+/// interrupted before its final rename.
+async fn remove_stray_tmp_files<E: std::error::Error>(path: &Path) -> Result<(), Error<E>> {
+    let Some(dir) = path.parent() else {
+        return Ok(());
+    };
+    let Some(file_name) = path.file_name().map(|name| name.to_string_lossy().into_owned()) else {
+        return Ok(());
+    };
+    let prefix = format!("{file_name}.tmp-");
+
+    let mut read_dir = match fs::read_dir(dir).await {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    while let Some(entry) = read_dir.next_entry().await? {
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            fs::remove_file(entry.path()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// An in-memory [`AsyncWrite`] sink shared via a mutex, used by the default
+/// [`AsyncFileRepr::stream_out`] implementation to recover the bytes written
+/// by [`AsyncFileRepr::flush`] (which otherwise consumes its writer by value).
+#[derive(Default, Clone)]
+struct VecSink(Arc<Mutex<Vec<u8>>>);
+impl VecSink {
+    fn into_inner(self) -> Vec<u8> {
+        Arc::try_unwrap(self.0)
+            .expect("no other VecSink handles should outlive the flush that wrote to it")
+            .into_inner()
+            .expect("VecSink mutex should never be poisoned")
+    }
+}
+impl AsyncWrite for VecSink {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, std::io::Error>> {
+        self.0.lock().expect("VecSink mutex should never be poisoned").extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), std::io::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), std::io::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    struct TestRepr(Vec<u8>);
+    #[async_trait]
+    impl AsyncFileRepr for TestRepr {
+        type Err = std::io::Error;
+
+        async fn load<R>(mut reader: R) -> Result<Self, Self::Err>
+        where
+            R: Send + Unpin + AsyncRead,
+        {
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf).await?;
+            Ok(Self(buf))
+        }
+
+        async fn flush<W>(self: &Arc<Self>, mut writer: W) -> Result<(), Self::Err>
+        where
+            W: Send + Unpin + AsyncWrite,
+        {
+            writer.write_all(&self.0).await?;
+            writer.flush().await
+        }
+    }
+
+    fn unique_tmp_path(label: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("decay-cache-test-{label}-{:016x}", fastrand::u64(..)))
+    }
+
+    #[tokio::test]
+    async fn flush_to_disk_writes_content_and_leaves_no_tmp_file() {
+        let path = unique_tmp_path("flush");
+        let value = Arc::new(TestRepr(b"hello crash safety".to_vec()));
+
+        value.flush_to_disk(&path).await.unwrap();
+
+        let contents = fs::read(&path).await.unwrap();
+        assert_eq!(contents, b"hello crash safety");
+
+        let tmp_prefix = format!("{}.tmp-", path.file_name().unwrap().to_string_lossy());
+        let mut read_dir = fs::read_dir(path.parent().unwrap()).await.unwrap();
+        while let Some(entry) = read_dir.next_entry().await.unwrap() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            assert!(!name.starts_with(&tmp_prefix), "stray temp file left behind: {name}");
+        }
+
+        TestRepr::delete(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn delete_removes_the_file_and_stray_tmp_siblings() {
+        let path = unique_tmp_path("delete");
+        fs::write(&path, b"real").await.unwrap();
+        let stray = sibling_tmp_path(&path);
+        fs::write(&stray, b"stray").await.unwrap();
+
+        TestRepr::delete(&path).await.unwrap();
+
+        assert!(!path.exists());
+        assert!(!stray.exists());
+    }
+}